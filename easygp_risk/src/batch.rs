@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crossbeam_channel::{unbounded, Receiver};
+
+use crate::engine::diagnose;
+use crate::types::{
+    ContinuousSymptom, DiagnosisResult, Feature, PatientObservation, SymptomFact, ThresholdConfig,
+};
+
+/// Wire format a [`WorkItem`] should be parsed as before diagnosis.
+///
+/// `Json` carries exactly one [`PatientObservation`] per item. `NdJson` and
+/// `Csv` carry one record per non-empty line, so a single item can expand
+/// into several diagnosis results (see [`run_batch`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    Json,
+    /// One CSV row per patient, no header:
+    /// `age,contact_history,discrete_symptoms,continuous_symptoms` where
+    /// `discrete_symptoms` is `;`-separated `Feature:true|false` pairs and
+    /// `continuous_symptoms` is `;`-separated `Feature:value` pairs (either
+    /// list may be empty). Feature names match the `Feature` variant names,
+    /// e.g. `SwollenGlands`.
+    Csv,
+    /// Newline-delimited JSON: one `PatientObservation` JSON document per
+    /// non-empty line.
+    NdJson,
+}
+
+/// The raw payload of a [`WorkItem`], in whichever shape the caller had it.
+pub enum InputItem {
+    Observation(PatientObservation),
+    Path(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+/// One unit of work for the batch pipeline: an identifier for the result
+/// map, the format to parse `item` as, and the item itself.
+pub struct WorkItem {
+    pub id: String,
+    pub format: InputFormat,
+    pub item: InputItem,
+}
+
+#[derive(Debug)]
+pub enum BatchError {
+    Io(std::io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::Io(err) => write!(f, "io error: {err}"),
+            BatchError::Parse(msg) => write!(f, "parse error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+impl From<std::io::Error> for BatchError {
+    fn from(err: std::io::Error) -> Self {
+        BatchError::Io(err)
+    }
+}
+
+/// Map a `Feature` variant's name (e.g. `"SwollenGlands"`) back to the
+/// variant, for the plain-text CSV format.
+fn feature_from_name(name: &str) -> Option<Feature> {
+    match name {
+        "Fever" => Some(Feature::Fever),
+        "SwollenGlands" => Some(Feature::SwollenGlands),
+        "Exudate" => Some(Feature::Exudate),
+        "Cough" => Some(Feature::Cough),
+        "Rash" => Some(Feature::Rash),
+        "SoreThroat" => Some(Feature::SoreThroat),
+        "Rhinorrhea" => Some(Feature::Rhinorrhea),
+        "Headache" => Some(Feature::Headache),
+        "TonsilSwelling" => Some(Feature::TonsilSwelling),
+        "LymphNodes" => Some(Feature::LymphNodes),
+        "Tenderness" => Some(Feature::Tenderness),
+        "Onset" => Some(Feature::Onset),
+        "PANDAS" => Some(Feature::PANDAS),
+        "Irritability" => Some(Feature::Irritability),
+        "Tics" => Some(Feature::Tics),
+        _ => None,
+    }
+}
+
+/// Parse one `age,contact_history,discrete_symptoms,continuous_symptoms`
+/// CSV row (see [`InputFormat::Csv`]) into a `PatientObservation`.
+fn parse_csv_record(line: &str) -> Result<PatientObservation, BatchError> {
+    let columns: Vec<&str> = line.splitn(4, ',').collect();
+    let [age, contact_history, discrete, continuous] = columns[..] else {
+        return Err(BatchError::Parse(format!(
+            "expected 4 CSV columns, got {}: {line:?}",
+            columns.len()
+        )));
+    };
+
+    let age = age
+        .trim()
+        .parse::<u8>()
+        .map_err(|err| BatchError::Parse(format!("invalid age {age:?}: {err}")))?;
+    let contact_history = match contact_history.trim() {
+        "true" | "1" => true,
+        "false" | "0" | "" => false,
+        other => return Err(BatchError::Parse(format!("invalid contact_history {other:?}"))),
+    };
+
+    let discrete_symptoms = discrete
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, present) = entry
+                .split_once(':')
+                .ok_or_else(|| BatchError::Parse(format!("invalid discrete entry {entry:?}")))?;
+            let feature = feature_from_name(name)
+                .ok_or_else(|| BatchError::Parse(format!("unknown feature {name:?}")))?;
+            let present = match present {
+                "true" => true,
+                "false" => false,
+                other => {
+                    return Err(BatchError::Parse(format!("invalid presence {other:?}")))
+                }
+            };
+            Ok(SymptomFact { feature, present })
+        })
+        .collect::<Result<Vec<_>, BatchError>>()?;
+
+    let continuous_symptoms = continuous
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, value) = entry
+                .split_once(':')
+                .ok_or_else(|| BatchError::Parse(format!("invalid continuous entry {entry:?}")))?;
+            let feature = feature_from_name(name)
+                .ok_or_else(|| BatchError::Parse(format!("unknown feature {name:?}")))?;
+            let value = value
+                .parse::<f32>()
+                .map_err(|err| BatchError::Parse(format!("invalid value {value:?}: {err}")))?;
+            if !value.is_finite() {
+                // Rust's `f32::parse` accepts "NaN"/"inf" literals; reject
+                // them here so a malformed row can't turn `engine::diagnose`'s
+                // probabilities into NaN.
+                return Err(BatchError::Parse(format!("non-finite value {value:?}")));
+            }
+            Ok(ContinuousSymptom { feature, value })
+        })
+        .collect::<Result<Vec<_>, BatchError>>()?;
+
+    Ok(PatientObservation {
+        age,
+        contact_history,
+        discrete_symptoms,
+        continuous_symptoms,
+    })
+}
+
+/// Parse `bytes` as `format`, returning one `PatientObservation` per record.
+/// `NdJson` and `Csv` treat each non-empty line as its own record; `Json`
+/// always yields exactly one.
+fn parse_records(format: InputFormat, bytes: &[u8]) -> Result<Vec<PatientObservation>, BatchError> {
+    match format {
+        InputFormat::Json => {
+            let observation = serde_json::from_slice(bytes)
+                .map_err(|err| BatchError::Parse(err.to_string()))?;
+            Ok(vec![observation])
+        }
+        InputFormat::NdJson => {
+            let text = std::str::from_utf8(bytes).map_err(|err| BatchError::Parse(err.to_string()))?;
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| {
+                    serde_json::from_str(line).map_err(|err| BatchError::Parse(err.to_string()))
+                })
+                .collect()
+        }
+        InputFormat::Csv => {
+            let text = std::str::from_utf8(bytes).map_err(|err| BatchError::Parse(err.to_string()))?;
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(parse_csv_record)
+                .collect()
+        }
+    }
+}
+
+fn resolve(work: &WorkItem) -> Result<Vec<PatientObservation>, BatchError> {
+    match &work.item {
+        InputItem::Observation(observation) => Ok(vec![observation.clone()]),
+        InputItem::Bytes(bytes) => parse_records(work.format, bytes),
+        InputItem::Path(path) => {
+            let bytes = fs::read(path)?;
+            parse_records(work.format, &bytes)
+        }
+    }
+}
+
+/// Score every `WorkItem` in `items`, spreading the work across `num_threads`
+/// workers fed by a shared queue, and return each record's result keyed by
+/// its `WorkItem::id` (or `"{id}#{n}"` for the `n`th record, counted from 0
+/// over non-empty lines, of an `NdJson`/`Csv` item holding more than one
+/// record). A parse failure is kept in the map as `Err` under the item's
+/// `id` rather than dropped; a failure anywhere in a multi-record item means
+/// none of that item's records are scored. `thresholds` is applied uniformly
+/// to every patient in the batch.
+pub fn run_batch(
+    items: Vec<WorkItem>,
+    num_threads: usize,
+    thresholds: ThresholdConfig,
+) -> HashMap<String, Result<DiagnosisResult, BatchError>> {
+    let (sender, receiver): (_, Receiver<WorkItem>) = unbounded();
+    for item in items {
+        sender.send(item).expect("receiver outlives all senders");
+    }
+    drop(sender);
+
+    let results = Arc::new(Mutex::new(HashMap::new()));
+
+    thread::scope(|scope| {
+        for _ in 0..num_threads.max(1) {
+            let receiver = receiver.clone();
+            let results = Arc::clone(&results);
+            scope.spawn(move || {
+                while let Ok(work) = receiver.recv() {
+                    match resolve(&work) {
+                        Ok(observations) if observations.len() == 1 => {
+                            let result = diagnose(&observations[0], &thresholds);
+                            results.lock().unwrap().insert(work.id.clone(), Ok(result));
+                        }
+                        Ok(observations) => {
+                            let mut map = results.lock().unwrap();
+                            for (index, observation) in observations.iter().enumerate() {
+                                let result = diagnose(observation, &thresholds);
+                                map.insert(format!("{}#{index}", work.id), Ok(result));
+                            }
+                        }
+                        Err(err) => {
+                            results.lock().unwrap().insert(work.id.clone(), Err(err));
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    Arc::try_unwrap(results)
+        .expect("all worker threads have joined")
+        .into_inner()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csv_record_with_discrete_and_continuous_symptoms() {
+        let observation =
+            parse_csv_record("7,true,Fever:true;SwollenGlands:false,Fever:38.5").unwrap();
+
+        assert_eq!(observation.age, 7);
+        assert!(observation.contact_history);
+        assert_eq!(observation.discrete_symptoms.len(), 2);
+        assert_eq!(observation.continuous_symptoms.len(), 1);
+        assert_eq!(observation.continuous_symptoms[0].value, 38.5);
+    }
+
+    #[test]
+    fn csv_record_rejects_unknown_feature_name() {
+        let err = parse_csv_record("7,false,NotAFeature:true,").unwrap_err();
+        assert!(matches!(err, BatchError::Parse(_)));
+    }
+
+    #[test]
+    fn csv_record_rejects_non_finite_continuous_value() {
+        let err = parse_csv_record("7,false,,Fever:NaN").unwrap_err();
+        assert!(matches!(err, BatchError::Parse(_)));
+    }
+
+    #[test]
+    fn run_batch_splits_ndjson_into_one_result_per_line() {
+        let ndjson = concat!(
+            r#"{"age":7,"contact_history":false,"discrete_symptoms":[],"continuous_symptoms":[]}"#,
+            "\n",
+            r#"{"age":30,"contact_history":true,"discrete_symptoms":[],"continuous_symptoms":[]}"#,
+        );
+        let items = vec![WorkItem {
+            id: "cohort".to_string(),
+            format: InputFormat::NdJson,
+            item: InputItem::Bytes(ndjson.as_bytes().to_vec()),
+        }];
+
+        let results = run_batch(items, 2, ThresholdConfig::default());
+
+        assert!(results.contains_key("cohort#0"));
+        assert!(results.contains_key("cohort#1"));
+        assert!(results["cohort#0"].is_ok());
+        assert!(results["cohort#1"].is_ok());
+    }
+
+    #[test]
+    fn run_batch_surfaces_parse_failures_instead_of_dropping_them() {
+        let items = vec![WorkItem {
+            id: "broken".to_string(),
+            format: InputFormat::Json,
+            item: InputItem::Bytes(b"not json".to_vec()),
+        }];
+
+        let results = run_batch(items, 1, ThresholdConfig::default());
+
+        assert!(matches!(results["broken"], Err(BatchError::Parse(_))));
+    }
+}
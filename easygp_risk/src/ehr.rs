@@ -0,0 +1,266 @@
+use std::fmt;
+
+use serde::de::{self, Deserializer, Expected, Visitor};
+use serde::Deserialize;
+
+use crate::types::{ContinuousSymptom, Feature, PatientObservation, SymptomFact};
+
+impl Feature {
+    /// Map an external coding-system string to the `Feature` it represents.
+    /// Unknown codes return `None` so callers can skip or log them instead
+    /// of failing the whole payload.
+    ///
+    /// The first block below are real SNOMED CT concept codes. The second
+    /// block (`Tenderness`, `Onset`, `PANDAS`, `Irritability`, `Tics`) has
+    /// no verified SNOMED/LOINC binding yet — those strings are
+    /// placeholders and will not match a real EHR export. Replace them
+    /// with real codes before relying on this mapping for those five
+    /// features.
+    pub fn from_external_code(code: &str) -> Option<Feature> {
+        match code {
+            "386661006" => Some(Feature::Fever),
+            "443497002" => Some(Feature::SwollenGlands),
+            "300191002" => Some(Feature::Exudate),
+            "49727002" => Some(Feature::Cough),
+            "271807003" => Some(Feature::Rash),
+            "267102003" => Some(Feature::SoreThroat),
+            "64531003" => Some(Feature::Rhinorrhea),
+            "25064002" => Some(Feature::Headache),
+            "162397003" => Some(Feature::TonsilSwelling),
+            "30746006" => Some(Feature::LymphNodes),
+
+            // PLACEHOLDER codes pending a verified SNOMED/LOINC mapping.
+            "PLACEHOLDER-TENDERNESS" => Some(Feature::Tenderness),
+            "PLACEHOLDER-ONSET" => Some(Feature::Onset),
+            "PLACEHOLDER-PANDAS" => Some(Feature::PANDAS),
+            "PLACEHOLDER-IRRITABILITY" => Some(Feature::Irritability),
+            "PLACEHOLDER-TICS" => Some(Feature::Tics),
+
+            _ => None,
+        }
+    }
+}
+
+/// Accepts a boolean encoded as a JSON bool, a `0`/`1` integer, or a
+/// `"true"`/`"false"`/`"1"`/`"0"` string, the way EHR exports tend to.
+struct LenientBool;
+
+impl<'de> Visitor<'de> for LenientBool {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a bool, 0/1 integer, or \"true\"/\"false\"/\"1\"/\"0\" string")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<bool, E> {
+        Ok(v)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<bool, E> {
+        Ok(v != 0)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<bool, E> {
+        Ok(v != 0)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<bool, E> {
+        match v {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            other => Err(de::Error::invalid_value(de::Unexpected::Str(other), &self)),
+        }
+    }
+}
+
+fn deserialize_lenient_bool<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    deserializer.deserialize_any(LenientBool)
+}
+
+/// Accepts a magnitude encoded as a JSON number or as a numeric string
+/// (e.g. `"38.5"`), the way a fever reading often comes back from an
+/// export.
+struct LenientF32;
+
+impl<'de> Visitor<'de> for LenientF32 {
+    type Value = f32;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a number or a numeric string")
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<f32, E> {
+        finite_or_err(v as f32, &self)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<f32, E> {
+        Ok(v as f32)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<f32, E> {
+        Ok(v as f32)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<f32, E> {
+        let parsed: f32 = v
+            .parse()
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(v), &self))?;
+        finite_or_err(parsed, &self)
+    }
+}
+
+/// Reject NaN/infinite magnitudes so they can never reach `engine::diagnose`
+/// — Rust's `f32::parse` happily accepts the literal strings `"NaN"`/
+/// `"inf"`, which an upstream export shouldn't be able to use to turn the
+/// scoring engine's probabilities into NaN.
+fn finite_or_err<E: de::Error>(value: f32, unexpected: &dyn Expected) -> Result<f32, E> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(de::Error::invalid_value(
+            de::Unexpected::Float(value as f64),
+            unexpected,
+        ))
+    }
+}
+
+fn deserialize_lenient_f32<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    deserializer.deserialize_any(LenientF32)
+}
+
+/// One row of an external symptom export: an unmapped feature code plus a
+/// presence flag in whatever shape the source system used.
+#[derive(Deserialize)]
+struct RawSymptomEntry {
+    code: String,
+    #[serde(deserialize_with = "deserialize_lenient_bool")]
+    present: bool,
+}
+
+/// One row of an external measurement export: an unmapped feature code
+/// plus a magnitude in whatever shape the source system used.
+#[derive(Deserialize)]
+struct RawMeasurementEntry {
+    code: String,
+    #[serde(deserialize_with = "deserialize_lenient_f32")]
+    value: f32,
+}
+
+/// The loosely-typed shape a real EHR export tends to arrive in: known
+/// fields may be missing, and symptom/measurement codes need mapping
+/// through [`Feature::from_external_code`] before they mean anything.
+#[derive(Deserialize)]
+struct RawPatientObservation {
+    #[serde(default)]
+    age: u8,
+    #[serde(default, deserialize_with = "deserialize_lenient_bool")]
+    contact_history: bool,
+    #[serde(default)]
+    symptoms: Vec<RawSymptomEntry>,
+    #[serde(default)]
+    measurements: Vec<RawMeasurementEntry>,
+}
+
+/// Parse a raw EHR export payload into a [`PatientObservation`], tolerating
+/// missing fields and the mixed bool/number-as-string encodings real
+/// exports use. Feature codes that don't map to a known [`Feature`] are
+/// dropped rather than failing the whole payload.
+pub fn from_ehr_json(raw: &str) -> Result<PatientObservation, serde_json::Error> {
+    let raw: RawPatientObservation = serde_json::from_str(raw)?;
+
+    let discrete_symptoms = raw
+        .symptoms
+        .into_iter()
+        .filter_map(|entry| {
+            Feature::from_external_code(&entry.code).map(|feature| SymptomFact {
+                feature,
+                present: entry.present,
+            })
+        })
+        .collect();
+
+    let continuous_symptoms = raw
+        .measurements
+        .into_iter()
+        .filter_map(|entry| {
+            Feature::from_external_code(&entry.code).map(|feature| ContinuousSymptom {
+                feature,
+                value: entry.value,
+            })
+        })
+        .collect();
+
+    Ok(PatientObservation {
+        age: raw.age,
+        contact_history: raw.contact_history,
+        discrete_symptoms,
+        continuous_symptoms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_finite_measurement_values() {
+        let result = from_ehr_json(r#"{"measurements": [{"code": "386661006", "value": "NaN"}]}"#);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn coerces_string_and_numeric_bools_and_numbers() {
+        let observation = from_ehr_json(
+            r#"{
+                "age": 7,
+                "contact_history": "1",
+                "symptoms": [
+                    {"code": "386661006", "present": "true"},
+                    {"code": "443497002", "present": 0}
+                ],
+                "measurements": [
+                    {"code": "386661006", "value": "38.5"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(observation.age, 7);
+        assert!(observation.contact_history);
+        assert_eq!(observation.discrete_symptoms.len(), 2);
+        assert!(observation
+            .discrete_symptoms
+            .iter()
+            .any(|fact| fact.feature == Feature::Fever && fact.present));
+        assert!(observation
+            .discrete_symptoms
+            .iter()
+            .any(|fact| fact.feature == Feature::SwollenGlands && !fact.present));
+        assert_eq!(observation.continuous_symptoms[0].value, 38.5);
+    }
+
+    #[test]
+    fn drops_unmapped_feature_codes_instead_of_failing() {
+        let observation = from_ehr_json(
+            r#"{
+                "symptoms": [
+                    {"code": "not-a-real-code", "present": true}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert!(observation.discrete_symptoms.is_empty());
+    }
+
+    #[test]
+    fn tolerates_missing_optional_fields() {
+        let observation = from_ehr_json("{}").unwrap();
+
+        assert_eq!(observation.age, 0);
+        assert!(!observation.contact_history);
+        assert!(observation.discrete_symptoms.is_empty());
+        assert!(observation.continuous_symptoms.is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+use std::io::{self, Write};
+
+use serde_json::json;
+
+use crate::types::{Condition, DiagnosisResult};
+
+/// Sink for a [`DiagnosisResult`], so callers don't have to hand-roll
+/// serialization for whichever downstream format their pipeline expects.
+pub trait Emitter {
+    fn emit(&mut self, result: &DiagnosisResult) -> io::Result<()>;
+}
+
+/// Emits one `DiagnosisResult` as a single JSON document, compact or
+/// pretty-printed depending on `pretty`.
+pub struct JsonEmitter<W: Write> {
+    pub writer: W,
+    pub pretty: bool,
+}
+
+impl<W: Write> JsonEmitter<W> {
+    pub fn new(writer: W, pretty: bool) -> Self {
+        Self { writer, pretty }
+    }
+}
+
+impl<W: Write> Emitter for JsonEmitter<W> {
+    fn emit(&mut self, result: &DiagnosisResult) -> io::Result<()> {
+        if self.pretty {
+            serde_json::to_writer_pretty(&mut self.writer, result)?;
+        } else {
+            serde_json::to_writer(&mut self.writer, result)?;
+        }
+        writeln!(self.writer)
+    }
+}
+
+/// Emits one compact JSON document per line, for streaming many patients'
+/// results into a single file or socket.
+pub struct NdjsonEmitter<W: Write> {
+    pub writer: W,
+}
+
+impl<W: Write> NdjsonEmitter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl<W: Write> Emitter for NdjsonEmitter<W> {
+    fn emit(&mut self, result: &DiagnosisResult) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, result)?;
+        writeln!(self.writer)
+    }
+}
+
+/// Maps a [`Condition`] to the ICD-10 code EHR pipelines expect.
+fn icd10_code(condition: Condition) -> &'static str {
+    match condition {
+        Condition::ViralPharyngitis => "J02.9",
+        Condition::StrepThroat => "J02.0",
+        Condition::InfectiousMono => "B27.90",
+        Condition::ScarletFever => "A38.9",
+        Condition::Covid19 => "U07.1",
+        Condition::AllergicRhinitis => "J30.9",
+        Condition::Influenza => "J11.1",
+        Condition::CommonCold => "J00",
+    }
+}
+
+/// Emits a `DiagnosisResult` as a minimal FHIR `Bundle`: one `Condition`
+/// resource per differential with an ICD-10 code, and one `Observation`
+/// resource per condition carrying its probability as a `valueQuantity`.
+pub struct FhirEmitter<W: Write> {
+    pub writer: W,
+}
+
+impl<W: Write> FhirEmitter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl<W: Write> Emitter for FhirEmitter<W> {
+    fn emit(&mut self, result: &DiagnosisResult) -> io::Result<()> {
+        let mut entries = Vec::new();
+
+        for (condition, probability) in &result.probabilities {
+            let code = icd10_code(*condition);
+            entries.push(json!({
+                "resource": {
+                    "resourceType": "Condition",
+                    "code": {
+                        "coding": [{
+                            "system": "http://hl7.org/fhir/sid/icd-10",
+                            "code": code,
+                            "display": format!("{condition:?}"),
+                        }]
+                    }
+                }
+            }));
+            entries.push(json!({
+                "resource": {
+                    "resourceType": "Observation",
+                    "code": {
+                        "coding": [{
+                            "system": "http://hl7.org/fhir/sid/icd-10",
+                            "code": code,
+                        }]
+                    },
+                    "valueQuantity": {
+                        "value": probability,
+                        "unit": "probability",
+                        "system": "http://unitsofmeasure.org",
+                        "code": "1",
+                    }
+                }
+            }));
+        }
+
+        let bundle = json!({
+            "resourceType": "Bundle",
+            "type": "collection",
+            "entry": entries,
+        });
+
+        serde_json::to_writer(&mut self.writer, &bundle)?;
+        writeln!(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::types::Recommendation;
+
+    fn sample_result() -> DiagnosisResult {
+        let mut probabilities = HashMap::new();
+        probabilities.insert(Condition::StrepThroat, 0.8);
+        let mut log_odds = HashMap::new();
+        log_odds.insert(Condition::StrepThroat, 1.4);
+
+        DiagnosisResult {
+            probabilities,
+            log_odds,
+            recommendation: Recommendation::PrescribeAntibiotics,
+            message: "StrepThroat is the leading differential.".to_string(),
+            explanation: "Most likely: StrepThroat.".to_string(),
+            evidence: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn pretty_json_emitter_output_differs_from_compact() {
+        let result = sample_result();
+
+        let mut compact = Vec::new();
+        JsonEmitter::new(&mut compact, false).emit(&result).unwrap();
+
+        let mut pretty = Vec::new();
+        JsonEmitter::new(&mut pretty, true).emit(&result).unwrap();
+
+        assert_ne!(compact, pretty);
+        assert!(pretty.len() > compact.len());
+    }
+
+    #[test]
+    fn ndjson_emitter_writes_one_compact_line_per_emit() {
+        let result = sample_result();
+        let mut buf = Vec::new();
+        let mut emitter = NdjsonEmitter::new(&mut buf);
+
+        emitter.emit(&result).unwrap();
+        emitter.emit(&result).unwrap();
+
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(serde_json::from_str::<serde_json::Value>(lines[0]).is_ok());
+    }
+
+    #[test]
+    fn fhir_emitter_produces_bundle_with_condition_and_observation() {
+        let result = sample_result();
+        let mut buf = Vec::new();
+        FhirEmitter::new(&mut buf).emit(&result).unwrap();
+
+        let bundle: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(bundle["resourceType"], "Bundle");
+
+        let entries = bundle["entry"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let condition_entry = entries
+            .iter()
+            .find(|entry| entry["resource"]["resourceType"] == "Condition")
+            .unwrap();
+        assert_eq!(
+            condition_entry["resource"]["code"]["coding"][0]["code"],
+            icd10_code(Condition::StrepThroat)
+        );
+
+        let observation_entry = entries
+            .iter()
+            .find(|entry| entry["resource"]["resourceType"] == "Observation")
+            .unwrap();
+        assert_eq!(
+            observation_entry["resource"]["code"]["coding"][0]["code"],
+            icd10_code(Condition::StrepThroat)
+        );
+        let value = observation_entry["resource"]["valueQuantity"]["value"]
+            .as_f64()
+            .unwrap();
+        assert!((value - 0.8).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+
+use crate::types::{
+    Condition, DiagnosisResult, EvidenceItem, Feature, PatientObservation, Polarity,
+    Recommendation, SymptomFact, ThresholdConfig,
+};
+
+const TOP_EVIDENCE_ITEMS: usize = 5;
+
+// NOTE: every prior/likelihood-ratio constant in this file (`prior_log_odds`,
+// `discrete_log_lr`, `continuous_log_lr_per_unit`, `age_log_lr`,
+// `contact_history_log_lr`) is an illustrative placeholder chosen to give
+// the naive-Bayes decomposition below plausible, internally-consistent
+// behavior on the textbook presentations covered by this module's tests.
+// None of it is sourced from validated clinical literature or sensitivity/
+// specificity studies, and it must not back real patient triage until a
+// clinician has reviewed and replaced it with numbers grounded in published
+// data.
+
+/// Baseline prevalence expressed as log-odds, i.e. `log(prior / (1 - prior))`.
+fn prior_log_odds(condition: Condition) -> f32 {
+    match condition {
+        Condition::ViralPharyngitis => 0.20,
+        Condition::StrepThroat => -0.60,
+        Condition::InfectiousMono => -1.80,
+        Condition::ScarletFever => -2.40,
+        Condition::Covid19 => -0.30,
+        Condition::AllergicRhinitis => -0.10,
+        Condition::Influenza => -0.50,
+        Condition::CommonCold => 0.40,
+    }
+}
+
+/// Log likelihood ratio `log(P(feature present | condition) / P(feature present | !condition))`
+/// contributed by a discrete feature, i.e. how much observing `feature` shifts
+/// the posterior log-odds of `condition`.
+fn discrete_log_lr(feature: Feature, condition: Condition) -> f32 {
+    use Condition::*;
+    use Feature::*;
+    match (feature, condition) {
+        (Fever, StrepThroat) => 0.70,
+        (Fever, ScarletFever) => 0.90,
+        (Fever, Influenza) => 0.80,
+        (Fever, Covid19) => 0.50,
+        (Fever, ViralPharyngitis) => 0.10,
+        (Fever, CommonCold) => -0.30,
+        (Fever, AllergicRhinitis) => -0.90,
+
+        (SwollenGlands, StrepThroat) => 0.60,
+        (SwollenGlands, InfectiousMono) => 1.10,
+
+        (Exudate, StrepThroat) => 0.90,
+        (Exudate, InfectiousMono) => 0.40,
+
+        (Cough, ViralPharyngitis) => 0.40,
+        (Cough, CommonCold) => 0.60,
+        (Cough, Influenza) => 0.50,
+        (Cough, StrepThroat) => -0.70,
+
+        (Rash, ScarletFever) => 2.50,
+        (Rash, AllergicRhinitis) => 0.30,
+
+        (SoreThroat, StrepThroat) => 0.50,
+        (SoreThroat, ViralPharyngitis) => 0.30,
+        (SoreThroat, InfectiousMono) => 0.40,
+
+        (Rhinorrhea, CommonCold) => 0.70,
+        (Rhinorrhea, AllergicRhinitis) => 0.80,
+        (Rhinorrhea, StrepThroat) => -0.60,
+
+        (Headache, Influenza) => 0.40,
+        (Headache, Covid19) => 0.30,
+
+        (TonsilSwelling, StrepThroat) => 0.70,
+        (TonsilSwelling, InfectiousMono) => 0.50,
+
+        (LymphNodes, InfectiousMono) => 1.20,
+        (LymphNodes, StrepThroat) => 0.40,
+
+        (Tenderness, StrepThroat) => 0.30,
+
+        (Onset, Influenza) => 0.50,
+        (Onset, Covid19) => 0.30,
+
+        (PANDAS, StrepThroat) => 0.80,
+        (Irritability, StrepThroat) => 0.20,
+        (Tics, StrepThroat) => 0.30,
+
+        _ => 0.0,
+    }
+}
+
+/// Per-unit log likelihood ratio for a continuous feature, scaled by the
+/// observed value before being added to the posterior log-odds.
+fn continuous_log_lr_per_unit(feature: Feature, condition: Condition) -> f32 {
+    use Condition::*;
+    use Feature::*;
+    match (feature, condition) {
+        (Fever, StrepThroat) => 0.15,
+        (Fever, ScarletFever) => 0.18,
+        (Fever, Influenza) => 0.16,
+        (Fever, Covid19) => 0.10,
+        (Fever, ViralPharyngitis) => 0.05,
+        (Fever, CommonCold) => -0.05,
+        (Fever, AllergicRhinitis) => -0.12,
+        _ => 0.0,
+    }
+}
+
+/// Log likelihood ratio contributed by the patient's age, reflecting that
+/// strep throat skews towards school-age children and infectious
+/// mononucleosis towards adolescents/young adults.
+fn age_log_lr(age: u8, condition: Condition) -> f32 {
+    match condition {
+        Condition::StrepThroat if (5..=15).contains(&age) => 0.30,
+        Condition::InfectiousMono if (15..=25).contains(&age) => 0.50,
+        _ => 0.0,
+    }
+}
+
+/// Log likelihood ratio contributed by a known exposure to someone
+/// infected, which shifts weight towards Covid-19.
+fn contact_history_log_lr(contact_history: bool, condition: Condition) -> f32 {
+    match condition {
+        Condition::Covid19 if contact_history => 0.60,
+        _ => 0.0,
+    }
+}
+
+fn sigmoid(log_odds: f32) -> f32 {
+    1.0 / (1.0 + (-log_odds).exp())
+}
+
+fn recommendation_for(
+    top: Condition,
+    probability: f32,
+    thresholds: &ThresholdConfig,
+) -> Recommendation {
+    match top {
+        Condition::InfectiousMono | Condition::ScarletFever
+            if probability > thresholds.refer_specialist =>
+        {
+            Recommendation::ReferSpecialist
+        }
+        Condition::StrepThroat if probability > thresholds.prescribe_antibiotics => {
+            Recommendation::PrescribeAntibiotics
+        }
+        Condition::StrepThroat if probability > thresholds.test_for_strep => {
+            Recommendation::TestForStrep
+        }
+        Condition::AllergicRhinitis if probability > thresholds.consider_alternatives => {
+            Recommendation::ConsiderAlternatives
+        }
+        _ => Recommendation::Watchful,
+    }
+}
+
+fn render_explanation(top: Condition, evidence: &[EvidenceItem]) -> String {
+    let mut summary = format!("Most likely: {top:?}.");
+    for item in evidence.iter().take(TOP_EVIDENCE_ITEMS) {
+        let verb = match item.polarity {
+            Polarity::Supports => "supports",
+            Polarity::Opposes => "opposes",
+        };
+        summary.push_str(&format!(
+            " {:?} {verb} ({:+.2});",
+            item.feature, item.contribution
+        ));
+    }
+    summary
+}
+
+/// Run the naive-Bayes differential diagnosis over a patient observation,
+/// returning per-condition probabilities/log-odds plus a ranked evidence
+/// breakdown for the leading condition. `thresholds` selects the
+/// `Recommendation` so institutions can tune cutoffs to local guidelines.
+pub fn diagnose(observation: &PatientObservation, thresholds: &ThresholdConfig) -> DiagnosisResult {
+    let conditions = [
+        Condition::ViralPharyngitis,
+        Condition::StrepThroat,
+        Condition::InfectiousMono,
+        Condition::ScarletFever,
+        Condition::Covid19,
+        Condition::AllergicRhinitis,
+        Condition::Influenza,
+        Condition::CommonCold,
+    ];
+
+    let mut log_odds: HashMap<Condition, f32> = HashMap::new();
+    for &condition in &conditions {
+        log_odds.insert(condition, prior_log_odds(condition));
+    }
+
+    for &condition in &conditions {
+        let entry = log_odds.get_mut(&condition).unwrap();
+        for fact in discrete_facts(observation) {
+            if fact.present {
+                *entry += discrete_log_lr(fact.feature, condition);
+            }
+        }
+        for symptom in &observation.continuous_symptoms {
+            *entry += continuous_log_lr_per_unit(symptom.feature, condition) * symptom.value;
+        }
+        *entry += age_log_lr(observation.age, condition);
+        *entry += contact_history_log_lr(observation.contact_history, condition);
+    }
+
+    let probabilities: HashMap<Condition, f32> = log_odds
+        .iter()
+        .map(|(&condition, &lo)| (condition, sigmoid(lo)))
+        .collect();
+
+    // `total_cmp` gives a total order even if a continuous symptom's value
+    // was NaN/infinite, so a single malformed reading can't panic the
+    // engine for the whole observation.
+    let top = conditions
+        .iter()
+        .copied()
+        .max_by(|a, b| probabilities[a].total_cmp(&probabilities[b]))
+        .unwrap();
+
+    let mut evidence: Vec<EvidenceItem> = Vec::new();
+    for fact in discrete_facts(observation) {
+        if !fact.present {
+            continue;
+        }
+        let contribution = discrete_log_lr(fact.feature, top);
+        if contribution == 0.0 {
+            continue;
+        }
+        evidence.push(EvidenceItem {
+            feature: fact.feature,
+            contribution,
+            polarity: if contribution >= 0.0 {
+                Polarity::Supports
+            } else {
+                Polarity::Opposes
+            },
+        });
+    }
+    for symptom in &observation.continuous_symptoms {
+        let contribution = continuous_log_lr_per_unit(symptom.feature, top) * symptom.value;
+        if contribution == 0.0 {
+            continue;
+        }
+        evidence.push(EvidenceItem {
+            feature: symptom.feature,
+            contribution,
+            polarity: if contribution >= 0.0 {
+                Polarity::Supports
+            } else {
+                Polarity::Opposes
+            },
+        });
+    }
+    evidence.sort_by(|a, b| b.contribution.abs().total_cmp(&a.contribution.abs()));
+
+    let explanation = render_explanation(top, &evidence);
+    let recommendation = recommendation_for(top, probabilities[&top], thresholds);
+
+    DiagnosisResult {
+        probabilities,
+        log_odds,
+        recommendation,
+        message: format!("{top:?} is the leading differential."),
+        explanation,
+        evidence,
+    }
+}
+
+fn discrete_facts(observation: &PatientObservation) -> impl Iterator<Item = &SymptomFact> {
+    observation.discrete_symptoms.iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ContinuousSymptom;
+
+    fn observation_with(features: &[(Feature, bool)]) -> PatientObservation {
+        PatientObservation {
+            age: 0,
+            contact_history: false,
+            discrete_symptoms: features
+                .iter()
+                .map(|&(feature, present)| SymptomFact { feature, present })
+                .collect(),
+            continuous_symptoms: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn nan_continuous_value_does_not_panic() {
+        let mut observation = observation_with(&[(Feature::Fever, true)]);
+        observation.continuous_symptoms.push(ContinuousSymptom {
+            feature: Feature::Fever,
+            value: f32::NAN,
+        });
+
+        // Must not panic; which condition "wins" in the presence of a NaN
+        // reading is unspecified, so we only assert it returns.
+        let _ = diagnose(&observation, &ThresholdConfig::default());
+    }
+
+    #[test]
+    fn fever_and_rash_top_differential_is_scarlet_fever() {
+        let observation =
+            observation_with(&[(Feature::Fever, true), (Feature::Rash, true)]);
+
+        let result = diagnose(&observation, &ThresholdConfig::default());
+
+        let top = result
+            .probabilities
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(&condition, _)| condition)
+            .unwrap();
+        assert_eq!(top, Condition::ScarletFever);
+    }
+
+    #[test]
+    fn swollen_glands_and_exudate_top_differential_is_strep_throat() {
+        let observation = observation_with(&[
+            (Feature::SwollenGlands, true),
+            (Feature::Exudate, true),
+        ]);
+
+        let result = diagnose(&observation, &ThresholdConfig::default());
+
+        let top = result
+            .probabilities
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(&condition, _)| condition)
+            .unwrap();
+        assert_eq!(top, Condition::StrepThroat);
+    }
+
+    #[test]
+    fn evidence_is_sorted_by_absolute_contribution_descending() {
+        let observation = observation_with(&[
+            (Feature::SwollenGlands, true),
+            (Feature::Exudate, true),
+            (Feature::Cough, true),
+        ]);
+
+        let result = diagnose(&observation, &ThresholdConfig::default());
+
+        let contributions: Vec<f32> = result.evidence.iter().map(|item| item.contribution.abs()).collect();
+        let mut sorted = contributions.clone();
+        sorted.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        assert_eq!(contributions, sorted);
+    }
+
+    #[test]
+    fn contact_history_shifts_covid_log_odds_up() {
+        let mut without_contact = observation_with(&[(Feature::Fever, true)]);
+        without_contact.contact_history = false;
+        let mut with_contact = without_contact.clone();
+        with_contact.contact_history = true;
+
+        let thresholds = ThresholdConfig::default();
+        let result_without = diagnose(&without_contact, &thresholds);
+        let result_with = diagnose(&with_contact, &thresholds);
+
+        assert!(
+            result_with.log_odds[&Condition::Covid19]
+                > result_without.log_odds[&Condition::Covid19]
+        );
+    }
+}
@@ -0,0 +1,13 @@
+mod batch;
+mod ehr;
+mod emit;
+mod engine;
+mod stream;
+mod types;
+
+pub use batch::{run_batch, BatchError, InputFormat, InputItem, WorkItem};
+pub use ehr::from_ehr_json;
+pub use emit::{Emitter, FhirEmitter, JsonEmitter, NdjsonEmitter};
+pub use engine::diagnose;
+pub use stream::{DiagnosisStream, SymptomUpdate};
+pub use types::*;
@@ -0,0 +1,221 @@
+use tokio::sync::{broadcast, mpsc};
+
+use crate::engine::diagnose;
+use crate::types::{
+    ContinuousSymptom, DiagnosisResult, PatientObservation, SymptomFact, ThresholdConfig,
+};
+
+/// An incremental update to a patient's observation, e.g. a new reading
+/// arriving from a bedside monitor.
+#[derive(Debug, Clone)]
+pub enum SymptomUpdate {
+    Discrete(SymptomFact),
+    Continuous(ContinuousSymptom),
+}
+
+/// Live monitoring engine: feed it `SymptomUpdate`s and it recomputes the
+/// diagnosis, broadcasting a fresh `DiagnosisResult` to every subscriber
+/// whenever a condition's probability moves by more than `delta`.
+pub struct DiagnosisStream {
+    updates: mpsc::UnboundedSender<SymptomUpdate>,
+    results: broadcast::Sender<DiagnosisResult>,
+}
+
+impl DiagnosisStream {
+    /// Spawn the background task that owns the latest `PatientObservation`
+    /// and re-runs the diagnosis as updates arrive.
+    pub fn spawn(initial: PatientObservation, delta: f32, thresholds: ThresholdConfig) -> Self {
+        let (updates_tx, mut updates_rx) = mpsc::unbounded_channel::<SymptomUpdate>();
+        let (results_tx, _) = broadcast::channel(16);
+
+        let results_tx_task = results_tx.clone();
+        tokio::spawn(async move {
+            let mut observation = initial;
+            let mut last = diagnose(&observation, &thresholds);
+
+            while let Some(update) = updates_rx.recv().await {
+                apply_update(&mut observation, update);
+                let next = diagnose(&observation, &thresholds);
+                if crossed_delta(&last, &next, delta) {
+                    let _ = results_tx_task.send(next.clone());
+                }
+                last = next;
+            }
+        });
+
+        Self {
+            updates: updates_tx,
+            results: results_tx,
+        }
+    }
+
+    /// Queue a new fact or reading for the background diagnosis task.
+    pub fn update(&self, update: SymptomUpdate) -> Result<(), mpsc::error::SendError<SymptomUpdate>> {
+        self.updates.send(update)
+    }
+
+    /// Subscribe to recomputed diagnoses. Each subscriber gets its own
+    /// receiver and only sees results broadcast after it subscribes.
+    pub fn subscribe(&self) -> broadcast::Receiver<DiagnosisResult> {
+        self.results.subscribe()
+    }
+}
+
+/// Merge an update into `observation`, replacing the prior fact/value for
+/// that feature if one already exists.
+fn apply_update(observation: &mut PatientObservation, update: SymptomUpdate) {
+    match update {
+        SymptomUpdate::Discrete(fact) => {
+            if let Some(existing) = observation
+                .discrete_symptoms
+                .iter_mut()
+                .find(|f| f.feature == fact.feature)
+            {
+                *existing = fact;
+            } else {
+                observation.discrete_symptoms.push(fact);
+            }
+        }
+        SymptomUpdate::Continuous(symptom) => {
+            if let Some(existing) = observation
+                .continuous_symptoms
+                .iter_mut()
+                .find(|s| s.feature == symptom.feature)
+            {
+                *existing = symptom;
+            } else {
+                observation.continuous_symptoms.push(symptom);
+            }
+        }
+    }
+}
+
+/// Whether any condition's probability moved by more than `delta` between
+/// two diagnoses, i.e. whether the update is worth broadcasting.
+fn crossed_delta(previous: &DiagnosisResult, next: &DiagnosisResult, delta: f32) -> bool {
+    next.probabilities.iter().any(|(condition, probability)| {
+        let prior = previous.probabilities.get(condition).copied().unwrap_or(0.0);
+        (probability - prior).abs() > delta
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::types::{Condition, Feature, Recommendation};
+
+    fn empty_observation() -> PatientObservation {
+        PatientObservation {
+            age: 0,
+            contact_history: false,
+            discrete_symptoms: Vec::new(),
+            continuous_symptoms: Vec::new(),
+        }
+    }
+
+    fn result_with(probabilities: &[(Condition, f32)]) -> DiagnosisResult {
+        DiagnosisResult {
+            probabilities: probabilities.iter().copied().collect(),
+            log_odds: HashMap::new(),
+            recommendation: Recommendation::Watchful,
+            message: String::new(),
+            explanation: String::new(),
+            evidence: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn apply_update_pushes_a_new_discrete_fact() {
+        let mut observation = empty_observation();
+        apply_update(
+            &mut observation,
+            SymptomUpdate::Discrete(SymptomFact {
+                feature: Feature::Fever,
+                present: true,
+            }),
+        );
+
+        assert_eq!(observation.discrete_symptoms.len(), 1);
+        assert!(observation.discrete_symptoms[0].present);
+    }
+
+    #[test]
+    fn apply_update_replaces_an_existing_discrete_fact() {
+        let mut observation = empty_observation();
+        observation.discrete_symptoms.push(SymptomFact {
+            feature: Feature::Fever,
+            present: true,
+        });
+
+        apply_update(
+            &mut observation,
+            SymptomUpdate::Discrete(SymptomFact {
+                feature: Feature::Fever,
+                present: false,
+            }),
+        );
+
+        assert_eq!(observation.discrete_symptoms.len(), 1);
+        assert!(!observation.discrete_symptoms[0].present);
+    }
+
+    #[test]
+    fn apply_update_replaces_an_existing_continuous_value() {
+        let mut observation = empty_observation();
+        observation.continuous_symptoms.push(ContinuousSymptom {
+            feature: Feature::Fever,
+            value: 37.0,
+        });
+
+        apply_update(
+            &mut observation,
+            SymptomUpdate::Continuous(ContinuousSymptom {
+                feature: Feature::Fever,
+                value: 39.5,
+            }),
+        );
+
+        assert_eq!(observation.continuous_symptoms.len(), 1);
+        assert_eq!(observation.continuous_symptoms[0].value, 39.5);
+    }
+
+    #[test]
+    fn crossed_delta_is_true_when_a_probability_moves_enough() {
+        let previous = result_with(&[(Condition::StrepThroat, 0.2)]);
+        let next = result_with(&[(Condition::StrepThroat, 0.5)]);
+
+        assert!(crossed_delta(&previous, &next, 0.1));
+    }
+
+    #[test]
+    fn crossed_delta_is_false_for_a_small_move() {
+        let previous = result_with(&[(Condition::StrepThroat, 0.2)]);
+        let next = result_with(&[(Condition::StrepThroat, 0.25)]);
+
+        assert!(!crossed_delta(&previous, &next, 0.5));
+    }
+
+    #[test]
+    fn spawned_stream_broadcasts_a_recomputed_diagnosis_on_update() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let stream = DiagnosisStream::spawn(empty_observation(), 0.0, ThresholdConfig::default());
+            let mut subscriber = stream.subscribe();
+
+            stream
+                .update(SymptomUpdate::Discrete(SymptomFact {
+                    feature: Feature::Fever,
+                    present: true,
+                }))
+                .unwrap();
+
+            let result = subscriber.recv().await.unwrap();
+            assert!(!result.probabilities.is_empty());
+        });
+    }
+}
@@ -57,7 +57,7 @@ pub struct PatientObservation {
 }
 
 /// Recommendation type based on probability thresholds
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum Recommendation {
     TestForStrep,
     PrescribeAntibiotics,
@@ -66,6 +66,126 @@ pub enum Recommendation {
     ReferSpecialist,
 }
 
+impl PartialOrd for Recommendation {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Recommendation {
+    /// Orders by `urgency()`, not variant declaration order, so a
+    /// `Vec<Recommendation>` sorts least to most pressing.
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.urgency().cmp(&other.urgency())
+    }
+}
+
+/// How urgently a [`Recommendation`] needs to be acted on, ordered from
+/// least to most pressing so a set of per-condition recommendations can
+/// be sorted and the most urgent one surfaced.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum Urgency {
+    Watchful,
+    Routine,
+    Urgent,
+    Emergent,
+}
+
+impl Urgency {
+    /// Explicit integer ranking backing `PartialOrd`/`Ord`, cheaper than
+    /// deriving and easier to keep intentional as tiers are added.
+    fn to_cmp_int(self) -> u8 {
+        match self {
+            Urgency::Watchful => 0,
+            Urgency::Routine => 1,
+            Urgency::Urgent => 2,
+            Urgency::Emergent => 3,
+        }
+    }
+}
+
+impl PartialEq for Urgency {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_cmp_int() == other.to_cmp_int()
+    }
+}
+
+impl Eq for Urgency {}
+
+impl PartialOrd for Urgency {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Urgency {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_cmp_int().cmp(&other.to_cmp_int())
+    }
+}
+
+impl Recommendation {
+    /// The urgency tier this recommendation falls into, from `Watchful`
+    /// at the lowest up to `Emergent` for specialist referral.
+    pub fn urgency(&self) -> Urgency {
+        match self {
+            Recommendation::ReferSpecialist => Urgency::Emergent,
+            Recommendation::PrescribeAntibiotics => Urgency::Urgent,
+            Recommendation::TestForStrep | Recommendation::ConsiderAlternatives => {
+                Urgency::Routine
+            }
+            Recommendation::Watchful => Urgency::Watchful,
+        }
+    }
+}
+
+/// Probability cutoffs used to select a [`Recommendation`], so an
+/// institution can tune them to local guidelines instead of relying on
+/// constants baked into the engine.
+#[derive(Debug, Clone, Copy)]
+pub struct ThresholdConfig {
+    /// Probability that `InfectiousMono`/`ScarletFever` must exceed before
+    /// recommending specialist referral.
+    pub refer_specialist: f32,
+    /// Probability that `StrepThroat` must exceed before recommending
+    /// antibiotics outright.
+    pub prescribe_antibiotics: f32,
+    /// Probability that `StrepThroat` must exceed before recommending a
+    /// confirmatory test.
+    pub test_for_strep: f32,
+    /// Probability that `AllergicRhinitis` must exceed before suggesting
+    /// alternative (non-infectious) management.
+    pub consider_alternatives: f32,
+}
+
+impl Default for ThresholdConfig {
+    fn default() -> Self {
+        Self {
+            refer_specialist: 0.5,
+            prescribe_antibiotics: 0.7,
+            test_for_strep: 0.3,
+            consider_alternatives: 0.5,
+        }
+    }
+}
+
+/// Whether an observed feature pushed the posterior toward or away from
+/// the leading condition.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Polarity {
+    Supports,
+    Opposes,
+}
+
+/// A single feature's contribution to the top condition's posterior
+/// log-odds, i.e. the log likelihood ratio `log(P(f|c)/P(f|!c))`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EvidenceItem {
+    pub feature: Feature,
+    pub contribution: f32,
+    pub polarity: Polarity,
+}
+
 /// Complete differential diagnosis result
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DiagnosisResult {
@@ -73,5 +193,52 @@ pub struct DiagnosisResult {
     pub log_odds: std::collections::HashMap<Condition, f32>,
     pub recommendation: Recommendation,
     pub message: String,
+    /// Rendered summary built from the top `evidence` items, kept for
+    /// callers that only want a human-readable string.
     pub explanation: String,
+    /// Per-feature log-likelihood-ratio terms for the top condition,
+    /// sorted descending by `contribution.abs()`.
+    pub evidence: Vec<EvidenceItem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recommendations_sort_least_to_most_urgent() {
+        let mut recommendations = vec![
+            Recommendation::ReferSpecialist,
+            Recommendation::Watchful,
+            Recommendation::PrescribeAntibiotics,
+            Recommendation::TestForStrep,
+        ];
+
+        recommendations.sort();
+
+        assert_eq!(
+            recommendations,
+            vec![
+                Recommendation::Watchful,
+                Recommendation::TestForStrep,
+                Recommendation::PrescribeAntibiotics,
+                Recommendation::ReferSpecialist,
+            ]
+        );
+    }
+
+    #[test]
+    fn most_urgent_recommendation_can_be_found_with_max() {
+        let recommendations = vec![
+            Recommendation::Watchful,
+            Recommendation::ConsiderAlternatives,
+            Recommendation::ReferSpecialist,
+            Recommendation::TestForStrep,
+        ];
+
+        assert_eq!(
+            recommendations.into_iter().max(),
+            Some(Recommendation::ReferSpecialist)
+        );
+    }
 }